@@ -0,0 +1,269 @@
+use super::{AuthProvider, Credentials, AZURE_CLI_CLIENT_ID};
+use crate::config::Cloud;
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use oauth2::basic::BasicClient;
+use oauth2::{
+    AuthUrl, AuthorizationCode, ClientId, CsrfToken, PkceCodeChallenge, RedirectUrl, Scope,
+    TokenResponse, TokenUrl,
+};
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use url::Url;
+
+/// Upper bound on how much of the redirect request we'll buffer while
+/// waiting for the end of the headers, so a misbehaving client can't make us
+/// allocate unbounded memory.
+const MAX_REDIRECT_REQUEST_BYTES: usize = 16 * 1024;
+
+/// Authorization Code + PKCE authentication provider.
+///
+/// Opens the user's browser at the Microsoft login page and catches the
+/// redirect on a short-lived loopback server, which is friendlier on
+/// desktops than copy-pasting a device code.
+pub struct AuthCodeAuth {
+    tenant_id: String,
+    client_id: String,
+    scope: String,
+    cloud: Cloud,
+}
+
+impl AuthCodeAuth {
+    pub fn new(tenant_id: String, client_id: Option<String>, cloud: &Cloud) -> Result<Self> {
+        let client_id = client_id.unwrap_or_else(|| AZURE_CLI_CLIENT_ID.to_string());
+
+        Ok(Self {
+            tenant_id,
+            client_id,
+            scope: cloud.cognitive_services_scope().to_string(),
+            cloud: *cloud,
+        })
+    }
+
+    async fn fetch_token(&self) -> Result<oauth2::basic::BasicTokenResponse> {
+        let login_endpoint = self.cloud.login_endpoint();
+
+        // Bind an ephemeral port up front so the redirect URI is known
+        // before we build the authorize URL.
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .map_err(|e| AppError::AuthCodeAuthFailed(format!("failed to bind loopback listener: {}", e)))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| AppError::AuthCodeAuthFailed(format!("failed to read listener address: {}", e)))?
+            .port();
+        let redirect_uri = format!("http://localhost:{}", port);
+
+        let auth_url = AuthUrl::new(format!(
+            "{}/{}/oauth2/v2.0/authorize",
+            login_endpoint, self.tenant_id
+        ))
+        .map_err(|e| AppError::AuthCodeAuthFailed(format!("Invalid auth URL: {}", e)))?;
+
+        let token_url = TokenUrl::new(format!(
+            "{}/{}/oauth2/v2.0/token",
+            login_endpoint, self.tenant_id
+        ))
+        .map_err(|e| AppError::AuthCodeAuthFailed(format!("Invalid token URL: {}", e)))?;
+
+        let client = BasicClient::new(
+            ClientId::new(self.client_id.clone()),
+            None,
+            auth_url,
+            Some(token_url),
+        )
+        .set_redirect_uri(
+            RedirectUrl::new(redirect_uri)
+                .map_err(|e| AppError::AuthCodeAuthFailed(format!("Invalid redirect URL: {}", e)))?,
+        );
+
+        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+
+        let (authorize_url, csrf_state) = client
+            .authorize_url(CsrfToken::new_random)
+            .add_scope(Scope::new(self.scope.clone()))
+            .set_pkce_challenge(pkce_challenge)
+            .url();
+
+        println!("Opening browser for sign-in...");
+        if webbrowser::open(authorize_url.as_str()).is_err() {
+            println!(
+                "Could not open a browser automatically. Please visit:\n  {}",
+                authorize_url
+            );
+        }
+
+        let (code, state) = Self::accept_redirect(listener).await?;
+
+        if state.secret() != csrf_state.secret() {
+            return Err(AppError::AuthCodeAuthFailed(
+                "redirect state did not match the expected value (possible CSRF)".to_string(),
+            ));
+        }
+
+        client
+            .exchange_code(code)
+            .set_pkce_verifier(pkce_verifier)
+            .request_async(oauth2::reqwest::async_http_client)
+            .await
+            .map_err(|e| AppError::AuthCodeAuthFailed(format!("Token exchange failed: {}", e)))
+    }
+
+    /// Accept the single inbound redirect from Azure AD, parse its `code`
+    /// and `state` query parameters, and respond with a page telling the
+    /// user they can close the tab.
+    async fn accept_redirect(listener: TcpListener) -> Result<(AuthorizationCode, CsrfToken)> {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| AppError::AuthCodeAuthFailed(format!("failed to accept redirect: {}", e)))?;
+
+        let request = Self::read_request_headers(&mut stream).await?;
+        let (code, state) = parse_redirect_request(&request)?;
+
+        let body =
+            "<html><body><h3>Authentication complete</h3><p>You may close this tab and return to the application.</p></body></html>";
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+
+        Ok((AuthorizationCode::new(code), CsrfToken::new(state)))
+    }
+
+    /// Read from `stream` until the full HTTP request headers have arrived
+    /// (a blank line terminates them) or [`MAX_REDIRECT_REQUEST_BYTES`] is
+    /// hit. A browser's GET isn't guaranteed to land in a single TCP read,
+    /// so a one-shot `stream.read` can hand `parse_redirect_request` a
+    /// truncated request line or query string.
+    async fn read_request_headers(stream: &mut TcpStream) -> Result<String> {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 512];
+
+        loop {
+            if let Some(end) = find_header_terminator(&buf) {
+                buf.truncate(end);
+                break;
+            }
+            if buf.len() >= MAX_REDIRECT_REQUEST_BYTES {
+                return Err(AppError::AuthCodeAuthFailed(
+                    "redirect request exceeded the maximum allowed size".to_string(),
+                ));
+            }
+
+            let n = stream.read(&mut chunk).await.map_err(|e| {
+                AppError::AuthCodeAuthFailed(format!("failed to read redirect request: {}", e))
+            })?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+}
+
+/// Find the end of the HTTP headers (the index right after the first blank
+/// line) in a partially-read request, if it's arrived yet.
+fn find_header_terminator(buf: &[u8]) -> Option<usize> {
+    buf.windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map(|pos| pos + 4)
+}
+
+/// Parse the `code` and `state` query parameters out of a raw HTTP request
+/// line (the request line plus headers, as read straight off the socket).
+/// Pulled out of `accept_redirect` so the parsing logic can be unit tested
+/// without standing up a real TCP listener.
+fn parse_redirect_request(request: &str) -> Result<(String, String)> {
+    let path = request
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .ok_or_else(|| AppError::AuthCodeAuthFailed("malformed redirect request".to_string()))?;
+
+    let url = Url::parse(&format!("http://localhost{}", path))
+        .map_err(|e| AppError::AuthCodeAuthFailed(format!("failed to parse redirect: {}", e)))?;
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+    let code = params.get("code").cloned().ok_or_else(|| {
+        AppError::AuthCodeAuthFailed("redirect was missing the 'code' parameter".to_string())
+    })?;
+    let state = params.get("state").cloned().ok_or_else(|| {
+        AppError::AuthCodeAuthFailed("redirect was missing the 'state' parameter".to_string())
+    })?;
+
+    Ok((code, state))
+}
+
+#[async_trait]
+impl AuthProvider for AuthCodeAuth {
+    async fn get_credentials(&self) -> Result<Credentials> {
+        let token = self.fetch_token().await?;
+        let expires_at = token.expires_in().map(|duration| Instant::now() + duration);
+
+        Ok(Credentials::BearerToken {
+            token: token.access_token().secret().clone(),
+            expires_at,
+        })
+    }
+
+    fn method_name(&self) -> &'static str {
+        "Authorization Code + PKCE"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_redirect_request_extracts_code_and_state() {
+        let request = "GET /?code=abc123&state=xyz789 HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let (code, state) = parse_redirect_request(request).unwrap();
+        assert_eq!(code, "abc123");
+        assert_eq!(state, "xyz789");
+    }
+
+    #[test]
+    fn test_parse_redirect_request_missing_code_rejected() {
+        let request = "GET /?state=xyz789 HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        assert!(parse_redirect_request(request).is_err());
+    }
+
+    #[test]
+    fn test_parse_redirect_request_missing_state_rejected() {
+        let request = "GET /?code=abc123 HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        assert!(parse_redirect_request(request).is_err());
+    }
+
+    #[test]
+    fn test_parse_redirect_request_malformed_request_line_rejected() {
+        assert!(parse_redirect_request("").is_err());
+        assert!(parse_redirect_request("garbage").is_err());
+    }
+
+    #[test]
+    fn test_find_header_terminator_found() {
+        let buf = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        assert_eq!(find_header_terminator(buf), Some(buf.len()));
+    }
+
+    #[test]
+    fn test_find_header_terminator_missing_when_headers_incomplete() {
+        let buf = b"GET / HTTP/1.1\r\nHost: localhost\r\n";
+        assert_eq!(find_header_terminator(buf), None);
+    }
+
+    #[test]
+    fn test_find_header_terminator_ignores_trailing_body() {
+        let buf = b"GET / HTTP/1.1\r\n\r\nextra-bytes-after-headers";
+        assert_eq!(find_header_terminator(buf), Some(18));
+    }
+}
@@ -0,0 +1,133 @@
+use super::{AuthProvider, Credentials};
+use crate::error::Result;
+use async_trait::async_trait;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Re-fetch a credential once it gets this close to expiring, rather than
+/// waiting until it has actually gone stale.
+const REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// Decorates an [`AuthProvider`], memoizing its result so repeated calls
+/// reuse the same credential until it is within [`REFRESH_MARGIN`] of
+/// `expires_at`, then transparently re-invokes the wrapped provider.
+pub struct CachingAuthProvider<A: AuthProvider> {
+    inner: A,
+    cached: RwLock<Option<Credentials>>,
+}
+
+impl<A: AuthProvider> CachingAuthProvider<A> {
+    pub fn new(inner: A) -> Self {
+        Self {
+            inner,
+            cached: RwLock::new(None),
+        }
+    }
+
+    /// A credential with no known expiry can't be proven fresh, so it is
+    /// never reused; one with a known expiry is fresh until it's within
+    /// `REFRESH_MARGIN` of that instant.
+    fn is_fresh(credentials: &Credentials) -> bool {
+        let Credentials::BearerToken { expires_at, .. } = credentials;
+        match expires_at {
+            Some(expires_at) => expires_at.saturating_duration_since(Instant::now()) > REFRESH_MARGIN,
+            None => false,
+        }
+    }
+}
+
+#[async_trait]
+impl<A: AuthProvider> AuthProvider for CachingAuthProvider<A> {
+    async fn get_credentials(&self) -> Result<Credentials> {
+        {
+            let cached = self.cached.read().await;
+            if let Some(credentials) = cached.as_ref() {
+                if Self::is_fresh(credentials) {
+                    return Ok(credentials.clone());
+                }
+            }
+        }
+
+        let fresh = self.inner.get_credentials().await?;
+        *self.cached.write().await = Some(fresh.clone());
+        Ok(fresh)
+    }
+
+    fn method_name(&self) -> &'static str {
+        self.inner.method_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// An `AuthProvider` that counts how many times it's invoked and hands
+    /// back a credential expiring `expires_in` from the moment it's called.
+    struct CountingAuthProvider {
+        calls: AtomicUsize,
+        expires_in: Duration,
+    }
+
+    #[async_trait]
+    impl AuthProvider for CountingAuthProvider {
+        async fn get_credentials(&self) -> Result<Credentials> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Credentials::BearerToken {
+                token: "token".to_string(),
+                expires_at: Some(Instant::now() + self.expires_in),
+            })
+        }
+
+        fn method_name(&self) -> &'static str {
+            "Counting"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reuses_credential_while_fresh() {
+        let provider = CachingAuthProvider::new(CountingAuthProvider {
+            calls: AtomicUsize::new(0),
+            expires_in: REFRESH_MARGIN + Duration::from_secs(60),
+        });
+
+        provider.get_credentials().await.unwrap();
+        provider.get_credentials().await.unwrap();
+
+        assert_eq!(provider.inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_refetches_once_within_refresh_margin() {
+        let provider = CachingAuthProvider::new(CountingAuthProvider {
+            calls: AtomicUsize::new(0),
+            // Already inside the refresh margin, so every call is treated
+            // as stale and re-fetched.
+            expires_in: REFRESH_MARGIN - Duration::from_secs(60),
+        });
+
+        provider.get_credentials().await.unwrap();
+        provider.get_credentials().await.unwrap();
+
+        assert_eq!(provider.inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_never_reuses_credential_with_no_known_expiry() {
+        let provider = CachingAuthProvider::new(CountingAuthProvider {
+            calls: AtomicUsize::new(0),
+            expires_in: Duration::from_secs(0),
+        });
+        // Force a `None` expiry by overwriting the cache directly.
+        provider.get_credentials().await.unwrap();
+        *provider.cached.write().await = Some(Credentials::BearerToken {
+            token: "token".to_string(),
+            expires_at: None,
+        });
+
+        provider.get_credentials().await.unwrap();
+
+        assert_eq!(provider.inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
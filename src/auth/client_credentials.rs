@@ -0,0 +1,137 @@
+use super::{AuthProvider, Credentials};
+use crate::config::Cloud;
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use oauth2::basic::{BasicClient, BasicTokenResponse};
+use oauth2::{AuthType, AuthUrl, ClientId, ClientSecret, Scope, TokenResponse, TokenUrl};
+use std::time::Instant;
+
+/// Environment variable the client secret is read from, so it never has to
+/// be written to a config file on disk.
+const CLIENT_SECRET_ENV_VAR: &str = "AZURE_CLIENT_SECRET";
+
+/// Client credentials (service principal) authentication provider.
+///
+/// Authenticates non-interactively as an Azure AD application registration,
+/// for use in CI/CD and other headless automation where neither the device
+/// code nor authorization code flows are usable.
+pub struct ClientCredentialsAuth {
+    tenant_id: String,
+    client_id: String,
+    client_secret: String,
+    scope: String,
+    cloud: Cloud,
+}
+
+impl ClientCredentialsAuth {
+    pub fn new(tenant_id: String, client_id: String, cloud: &Cloud) -> Result<Self> {
+        let client_secret = std::env::var(CLIENT_SECRET_ENV_VAR).map_err(|_| {
+            AppError::ClientCredentialsAuthFailed(format!(
+                "{} environment variable is not set",
+                CLIENT_SECRET_ENV_VAR
+            ))
+        })?;
+
+        Ok(Self {
+            tenant_id,
+            client_id,
+            client_secret,
+            scope: cloud.cognitive_services_scope().to_string(),
+            cloud: *cloud,
+        })
+    }
+
+    async fn fetch_token(&self) -> Result<BasicTokenResponse> {
+        let login_endpoint = self.cloud.login_endpoint();
+
+        let auth_url = AuthUrl::new(format!(
+            "{}/{}/oauth2/v2.0/authorize",
+            login_endpoint, self.tenant_id
+        ))
+        .map_err(|e| AppError::ClientCredentialsAuthFailed(format!("Invalid auth URL: {}", e)))?;
+
+        let token_url = TokenUrl::new(format!(
+            "{}/{}/oauth2/v2.0/token",
+            login_endpoint, self.tenant_id
+        ))
+        .map_err(|e| AppError::ClientCredentialsAuthFailed(format!("Invalid token URL: {}", e)))?;
+
+        let client = BasicClient::new(
+            ClientId::new(self.client_id.clone()),
+            Some(ClientSecret::new(self.client_secret.clone())),
+            auth_url,
+            Some(token_url),
+        )
+        // Azure AD's v2.0 token endpoint expects `client_id`/`client_secret`
+        // as form fields, not an `Authorization: Basic` header — the latter
+        // fails with AADSTS7000218 ("client_secret is missing").
+        .set_auth_type(AuthType::RequestBody);
+
+        client
+            .exchange_client_credentials()
+            .add_scope(Scope::new(self.scope.clone()))
+            .request_async(oauth2::reqwest::async_http_client)
+            .await
+            .map_err(|e| {
+                AppError::ClientCredentialsAuthFailed(format!("Token request failed: {}", e))
+            })
+    }
+}
+
+#[async_trait]
+impl AuthProvider for ClientCredentialsAuth {
+    async fn get_credentials(&self) -> Result<Credentials> {
+        let token = self.fetch_token().await?;
+        let expires_at = token.expires_in().map(|duration| Instant::now() + duration);
+
+        Ok(Credentials::BearerToken {
+            token: token.access_token().secret().clone(),
+            expires_at,
+        })
+    }
+
+    fn method_name(&self) -> &'static str {
+        "Client Credentials"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tests that touch CLIENT_SECRET_ENV_VAR run serially to avoid
+    // clobbering each other's env var state.
+    use std::sync::Mutex;
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_missing_secret_rejected() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::remove_var(CLIENT_SECRET_ENV_VAR);
+
+        let auth = ClientCredentialsAuth::new(
+            "tenant-id".to_string(),
+            "client-id".to_string(),
+            &Cloud::Global,
+        );
+        assert!(auth.is_err());
+    }
+
+    #[test]
+    fn test_secret_read_from_env() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::set_var(CLIENT_SECRET_ENV_VAR, "super-secret");
+
+        let auth = ClientCredentialsAuth::new(
+            "tenant-id".to_string(),
+            "client-id".to_string(),
+            &Cloud::China,
+        );
+        assert!(auth.is_ok());
+        let auth = auth.unwrap();
+        assert_eq!(auth.client_secret, "super-secret");
+        assert!(auth.scope.contains("cognitiveservices.azure.cn"));
+
+        std::env::remove_var(CLIENT_SECRET_ENV_VAR);
+    }
+}
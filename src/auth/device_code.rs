@@ -1,18 +1,25 @@
-use super::{AuthProvider, Credentials};
+use super::token_store::{CachedToken, TokenStore};
+use super::{instant_from_epoch_secs, AuthProvider, Credentials, AZURE_CLI_CLIENT_ID};
 use crate::config::Cloud;
 use crate::error::{AppError, Result};
 use async_trait::async_trait;
 use oauth2::basic::{BasicClient, BasicTokenResponse};
 use oauth2::devicecode::StandardDeviceAuthorizationResponse;
 use oauth2::{
-    AuthUrl, ClientId, DeviceAuthorizationUrl, DeviceCodeErrorResponseType, RequestTokenError,
-    Scope, TokenResponse, TokenUrl,
+    AuthUrl, ClientId, DeviceAuthorizationUrl, DeviceCodeErrorResponseType, RefreshToken,
+    RequestTokenError, Scope, TokenResponse, TokenUrl,
 };
-use std::time::Duration;
-use tokio::time::sleep;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-/// Azure CLI's well-known public client ID
-const AZURE_CLI_CLIENT_ID: &str = "04b07795-8ddb-461a-bbee-02f9e1bf7b46";
+/// Progress reported during the device code flow, for consumers that want
+/// to render their own UI instead of the default console banner.
+#[derive(Debug, Clone)]
+pub struct DeviceCodeProgress {
+    pub verification_uri: String,
+    pub user_code: String,
+    pub expires_in: Duration,
+}
 
 /// Device Code Flow authentication provider
 /// Displays a code for the user to enter at a Microsoft login page
@@ -21,27 +28,129 @@ pub struct DeviceCodeAuth {
     client_id: String,
     scope: String,
     cloud: Cloud,
+    token_store: TokenStore,
+    on_progress: Option<Arc<dyn Fn(DeviceCodeProgress) + Send + Sync>>,
 }
 
 impl DeviceCodeAuth {
     pub fn new(tenant_id: String, client_id: Option<String>, cloud: &Cloud) -> Result<Self> {
         let client_id = client_id.unwrap_or_else(|| AZURE_CLI_CLIENT_ID.to_string());
 
-        let scope = match cloud {
-            Cloud::Global => "https://cognitiveservices.azure.com/.default",
-            Cloud::China => "https://cognitiveservices.azure.cn/.default",
-        };
-
         Ok(Self {
             tenant_id,
             client_id,
-            scope: scope.to_string(),
+            scope: cloud.cognitive_services_scope().to_string(),
             cloud: *cloud,
+            token_store: TokenStore::new()?,
+            on_progress: None,
         })
     }
 
-    /// Fetch token using device code flow
-    async fn fetch_token(&self) -> Result<String> {
+    /// Register a callback invoked with [`DeviceCodeProgress`] instead of
+    /// the default console banner, so library consumers can render the
+    /// verification URI, user code, and countdown themselves.
+    pub fn with_progress_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(DeviceCodeProgress) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Fetch a usable access token, preferring the on-disk cache over a full
+    /// interactive round-trip: a still-valid cached token is returned as-is,
+    /// an expired one is silently renewed via its refresh token, and the
+    /// device code flow runs only when no cached entry is usable (none
+    /// stored, no refresh token, or the refresh failed).
+    async fn fetch_token(&self) -> Result<CachedToken> {
+        if let Some(cached) = self
+            .token_store
+            .load(&self.tenant_id, &self.client_id, &self.scope)
+        {
+            if cached.is_valid() {
+                return Ok(cached);
+            }
+            if let Some(refresh_token) = cached.refresh_token.clone() {
+                if let Ok(refreshed) = self.refresh_token(&refresh_token).await {
+                    return Ok(refreshed);
+                }
+                // Refresh token was revoked or expired; fall through to the
+                // interactive flow below.
+            }
+        }
+
+        self.fetch_token_interactive().await
+    }
+
+    /// Exchange a cached refresh token for a new access token, without any
+    /// user interaction.
+    async fn refresh_token(&self, refresh_token: &str) -> Result<CachedToken> {
+        let login_endpoint = self.cloud.login_endpoint();
+
+        let token_url = TokenUrl::new(format!(
+            "{}/{}/oauth2/v2.0/token",
+            login_endpoint, self.tenant_id
+        ))
+        .map_err(|e| AppError::DeviceCodeAuthFailed(format!("Invalid token URL: {}", e)))?;
+
+        let auth_url = AuthUrl::new(format!(
+            "{}/{}/oauth2/v2.0/authorize",
+            login_endpoint, self.tenant_id
+        ))
+        .map_err(|e| AppError::DeviceCodeAuthFailed(format!("Invalid auth URL: {}", e)))?;
+
+        let client = BasicClient::new(
+            ClientId::new(self.client_id.clone()),
+            None,
+            auth_url,
+            Some(token_url),
+        );
+
+        let token = client
+            .exchange_refresh_token(&RefreshToken::new(refresh_token.to_string()))
+            .request_async(oauth2::reqwest::async_http_client)
+            .await
+            .map_err(|e| AppError::DeviceCodeAuthFailed(format!("Token refresh failed: {}", e)))?;
+
+        self.cache_token(&token, Some(refresh_token.to_string()))
+    }
+
+    /// Persist a token response to the on-disk store, keeping the previous
+    /// refresh token if Azure didn't issue a new one, and return the entry
+    /// that was saved.
+    fn cache_token(
+        &self,
+        token: &BasicTokenResponse,
+        fallback_refresh_token: Option<String>,
+    ) -> Result<CachedToken> {
+        let expires_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            + token.expires_in().map(|d| d.as_secs()).unwrap_or(0);
+
+        let refresh_token = token
+            .refresh_token()
+            .map(|rt| rt.secret().clone())
+            .or(fallback_refresh_token);
+
+        let cached = CachedToken {
+            access_token: token.access_token().secret().clone(),
+            refresh_token,
+            expires_at,
+        };
+
+        self.token_store.save(
+            &self.tenant_id,
+            &self.client_id,
+            &self.scope,
+            cached.clone(),
+        )?;
+        Ok(cached)
+    }
+
+    /// Run the full interactive device code flow.
+    async fn fetch_token_interactive(&self) -> Result<CachedToken> {
         let login_endpoint = self.cloud.login_endpoint();
 
         // Build OAuth2 client
@@ -78,6 +187,9 @@ impl DeviceCodeAuth {
                 AppError::DeviceCodeAuthFailed(format!("Failed to initiate device code flow: {}", e))
             })?
             .add_scope(Scope::new(self.scope.clone()))
+            // Needed so Azure actually issues a refresh token alongside the
+            // access token, which is what lets later runs skip this flow.
+            .add_scope(Scope::new("offline_access".to_string()))
             .request_async(oauth2::reqwest::async_http_client)
             .await
             .map_err(|e| {
@@ -90,11 +202,21 @@ impl DeviceCodeAuth {
         // Poll for token
         let token = self.poll_for_token(&client, &details).await?;
 
-        Ok(token.access_token().secret().clone())
+        self.cache_token(&token, None)
     }
 
-    /// Display authentication instructions to the user
+    /// Report authentication instructions, either via the registered
+    /// progress callback or, by default, a console banner.
     fn display_instructions(&self, details: &StandardDeviceAuthorizationResponse) {
+        if let Some(callback) = &self.on_progress {
+            callback(DeviceCodeProgress {
+                verification_uri: details.verification_uri().as_str().to_string(),
+                user_code: details.user_code().secret().clone(),
+                expires_in: details.expires_in(),
+            });
+            return;
+        }
+
         println!("\n{}", "=".repeat(70));
         println!("  Azure Authentication Required");
         println!("{}", "=".repeat(70));
@@ -109,80 +231,53 @@ impl DeviceCodeAuth {
         println!();
     }
 
-    /// Poll the token endpoint until the user completes authentication
+    /// Poll the token endpoint until the user completes authentication.
+    ///
+    /// `request_async` already implements the full RFC 8628 polling
+    /// contract on its own: it sleeps between attempts using the `sleep_fn`
+    /// we pass it, retries on `authorization_pending`, and permanently
+    /// increases its internal interval by 5 seconds on each `slow_down`.
+    /// Passing `details.expires_in()` as the timeout means it gives up
+    /// exactly when the device code itself expires, rather than on an
+    /// arbitrary constant.
     async fn poll_for_token(
         &self,
         client: &BasicClient,
         details: &StandardDeviceAuthorizationResponse,
     ) -> Result<BasicTokenResponse> {
-        let interval = details.interval();
-        let timeout = Duration::from_secs(15 * 60); // 15 minutes
-        let start = std::time::Instant::now();
-
-        loop {
-            if start.elapsed() > timeout {
-                return Err(AppError::DeviceCodeAuthFailed(
-                    "Authentication timeout (15 minutes). Please try again.".to_string(),
-                ));
-            }
-
-            sleep(interval).await;
-
-            match client
-                .exchange_device_access_token(details)
-                .request_async(
-                    oauth2::reqwest::async_http_client,
-                    tokio::time::sleep,
-                    None,
-                )
-                .await
-            {
-                Ok(token) => {
-                    println!("✓ Authentication successful!\n");
-                    return Ok(token);
-                }
-                Err(RequestTokenError::ServerResponse(err)) => {
-                    match err.error() {
-                        DeviceCodeErrorResponseType::AuthorizationPending => {
-                            // Still waiting for user - continue polling
-                            continue;
-                        }
-                        DeviceCodeErrorResponseType::SlowDown => {
-                            // Server requested slower polling - add extra delay
-                            sleep(interval).await;
-                            continue;
-                        }
-                        DeviceCodeErrorResponseType::ExpiredToken => {
-                            return Err(AppError::DeviceCodeAuthFailed(
-                                "Device code expired. Please try again.".to_string(),
-                            ));
-                        }
-                        DeviceCodeErrorResponseType::AccessDenied => {
-                            return Err(AppError::DeviceCodeAuthFailed(
-                                "User declined authorization".to_string(),
-                            ));
-                        }
-                        _ => {
-                            return Err(AppError::DeviceCodeAuthFailed(format!(
-                                "Server error: {:?}",
-                                err
-                            )));
-                        }
-                    }
-                }
-                Err(RequestTokenError::Request(e)) => {
-                    return Err(AppError::DeviceCodeAuthFailed(format!(
-                        "Network error during token request: {}",
-                        e
-                    )));
-                }
-                Err(e) => {
-                    return Err(AppError::DeviceCodeAuthFailed(format!(
-                        "Token request failed: {}",
-                        e
-                    )));
-                }
+        match client
+            .exchange_device_access_token(details)
+            .request_async(
+                oauth2::reqwest::async_http_client,
+                tokio::time::sleep,
+                Some(details.expires_in()),
+            )
+            .await
+        {
+            Ok(token) => {
+                println!("✓ Authentication successful!\n");
+                Ok(token)
             }
+            Err(RequestTokenError::ServerResponse(err)) => match err.error() {
+                DeviceCodeErrorResponseType::ExpiredToken => Err(AppError::DeviceCodeAuthFailed(
+                    "Device code expired. Please try again.".to_string(),
+                )),
+                DeviceCodeErrorResponseType::AccessDenied => Err(AppError::DeviceCodeAuthFailed(
+                    "User declined authorization".to_string(),
+                )),
+                _ => Err(AppError::DeviceCodeAuthFailed(format!(
+                    "Server error: {:?}",
+                    err
+                ))),
+            },
+            Err(RequestTokenError::Request(e)) => Err(AppError::DeviceCodeAuthFailed(format!(
+                "Network error during token request: {}",
+                e
+            ))),
+            Err(e) => Err(AppError::DeviceCodeAuthFailed(format!(
+                "Token request failed: {}",
+                e
+            ))),
         }
     }
 }
@@ -190,8 +285,11 @@ impl DeviceCodeAuth {
 #[async_trait]
 impl AuthProvider for DeviceCodeAuth {
     async fn get_credentials(&self) -> Result<Credentials> {
-        let token = self.fetch_token().await?;
-        Ok(Credentials::BearerToken(token))
+        let cached = self.fetch_token().await?;
+        Ok(Credentials::BearerToken {
+            token: cached.access_token,
+            expires_at: Some(instant_from_epoch_secs(cached.expires_at)),
+        })
     }
 
     fn method_name(&self) -> &'static str {
@@ -1,37 +1,90 @@
-use super::{AuthProvider, Credentials};
+use super::{instant_from_epoch_secs, AuthProvider, Credentials};
+use crate::config::Cloud;
 use crate::error::{AppError, Result};
 use async_trait::async_trait;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    exp: u64,
+    aud: Option<String>,
+}
 
 /// Manual token authentication provider
-/// Accepts a pre-obtained bearer token and returns it for authentication
+/// Accepts a pre-obtained bearer token, validates it, and returns it for authentication
 pub struct ManualTokenAuth {
     token: String,
+    exp: u64,
 }
 
 impl ManualTokenAuth {
-    pub fn new(token: String) -> Result<Self> {
-        // Basic validation: not empty, reasonable length
+    pub fn new(token: String, cloud: &Cloud) -> Result<Self> {
         if token.trim().is_empty() {
             return Err(AppError::InvalidBearerToken(
                 "Token cannot be empty".to_string(),
             ));
         }
 
-        // Basic sanity check for token length (JWT tokens are typically > 50 chars)
-        if token.len() < 20 {
-            return Err(AppError::InvalidBearerToken(
-                "Token appears to be too short".to_string(),
-            ));
+        let claims = Self::decode_claims(&token)?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if claims.exp <= now {
+            return Err(AppError::ExpiredToken {
+                expired_at: claims.exp,
+            });
+        }
+
+        if let Some(aud) = &claims.aud {
+            let expected_audience = match cloud {
+                Cloud::Global => "https://cognitiveservices.azure.com",
+                Cloud::China => "https://cognitiveservices.azure.cn",
+            };
+            if aud.trim_end_matches('/') != expected_audience {
+                eprintln!(
+                    "warning: bearer token audience '{}' does not match the expected '{}' for this cloud",
+                    aud, expected_audience
+                );
+            }
         }
 
-        Ok(Self { token })
+        Ok(Self {
+            token,
+            exp: claims.exp,
+        })
+    }
+
+    /// Decode a JWT's header and claims without verifying its signature
+    /// (the resource server does that); this only checks that the token is
+    /// structurally a well-formed JWT and extracts `exp`/`aud`.
+    fn decode_claims(token: &str) -> Result<Claims> {
+        decode_header(token)
+            .map_err(|e| AppError::InvalidBearerToken(format!("malformed JWT header: {}", e)))?;
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.algorithms = vec![Algorithm::RS256, Algorithm::HS256, Algorithm::ES256];
+        validation.insecure_disable_signature_validation();
+        validation.validate_exp = false;
+        validation.validate_aud = false;
+
+        let data = decode::<Claims>(token, &DecodingKey::from_secret(&[]), &validation)
+            .map_err(|e| AppError::InvalidBearerToken(format!("malformed JWT claims: {}", e)))?;
+
+        Ok(data.claims)
     }
 }
 
 #[async_trait]
 impl AuthProvider for ManualTokenAuth {
     async fn get_credentials(&self) -> Result<Credentials> {
-        Ok(Credentials::BearerToken(self.token.clone()))
+        Ok(Credentials::BearerToken {
+            token: self.token.clone(),
+            expires_at: Some(instant_from_epoch_secs(self.exp)),
+        })
     }
 
     fn method_name(&self) -> &'static str {
@@ -43,33 +96,58 @@ impl AuthProvider for ManualTokenAuth {
 mod tests {
     use super::*;
 
+    const FUTURE_GLOBAL: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJleHAiOjk5OTk5OTk5OTksImF1ZCI6Imh0dHBzOi8vY29nbml0aXZlc2VydmljZXMuYXp1cmUuY29tIn0.sig";
+    const FUTURE_CHINA: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJleHAiOjk5OTk5OTk5OTksImF1ZCI6Imh0dHBzOi8vY29nbml0aXZlc2VydmljZXMuYXp1cmUuY24ifQ.sig";
+    const EXPIRED: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJleHAiOjEwMDAwMDAwMDAsImF1ZCI6Imh0dHBzOi8vY29nbml0aXZlc2VydmljZXMuYXp1cmUuY29tIn0.sig";
+    const MISMATCHED_AUD: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJleHAiOjk5OTk5OTk5OTksImF1ZCI6Imh0dHBzOi8vbWFuYWdlbWVudC5henVyZS5jb20ifQ.sig";
+
     #[test]
     fn test_empty_token_rejected() {
-        let result = ManualTokenAuth::new("".to_string());
+        let result = ManualTokenAuth::new("".to_string(), &Cloud::Global);
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_short_token_rejected() {
-        let result = ManualTokenAuth::new("short".to_string());
+    fn test_malformed_token_rejected() {
+        let result = ManualTokenAuth::new("not-a-jwt".to_string(), &Cloud::Global);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_expired_token_rejected() {
+        let result = ManualTokenAuth::new(EXPIRED.to_string(), &Cloud::Global);
+        assert!(matches!(result, Err(AppError::ExpiredToken { .. })));
+    }
+
     #[test]
     fn test_valid_token_accepted() {
-        let token = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiaWF0IjoxNTE2MjM5MDIyfQ";
-        let result = ManualTokenAuth::new(token.to_string());
+        let result = ManualTokenAuth::new(FUTURE_GLOBAL.to_string(), &Cloud::Global);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_china_cloud_audience_accepted() {
+        let result = ManualTokenAuth::new(FUTURE_CHINA.to_string(), &Cloud::China);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_audience_mismatch_warns_but_does_not_reject() {
+        // A mismatched `aud` is surfaced as a warning, not a hard failure,
+        // since the resource server is the final authority on audience.
+        let result = ManualTokenAuth::new(MISMATCHED_AUD.to_string(), &Cloud::Global);
         assert!(result.is_ok());
     }
 
     #[tokio::test]
     async fn test_get_credentials() {
-        let token = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiaWF0IjoxNTE2MjM5MDIyfQ";
-        let auth = ManualTokenAuth::new(token.to_string()).unwrap();
+        let auth = ManualTokenAuth::new(FUTURE_GLOBAL.to_string(), &Cloud::Global).unwrap();
         let creds = auth.get_credentials().await.unwrap();
         match creds {
-            Credentials::BearerToken(t) => assert_eq!(t, token),
-            _ => panic!("Expected bearer token credentials"),
+            Credentials::BearerToken { token, expires_at } => {
+                assert_eq!(token, FUTURE_GLOBAL);
+                assert!(expires_at.is_some());
+            }
         }
     }
 }
@@ -0,0 +1,60 @@
+mod auth_code;
+mod caching;
+mod client_credentials;
+mod device_code;
+mod manual_token;
+mod token_store;
+
+pub use auth_code::AuthCodeAuth;
+pub use caching::CachingAuthProvider;
+pub use client_credentials::ClientCredentialsAuth;
+pub use device_code::DeviceCodeAuth;
+pub use manual_token::ManualTokenAuth;
+
+use crate::error::Result;
+use async_trait::async_trait;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Azure CLI's well-known public client ID, used as the default `client_id`
+/// by the interactive auth providers.
+pub(crate) const AZURE_CLI_CLIENT_ID: &str = "04b07795-8ddb-461a-bbee-02f9e1bf7b46";
+
+/// Credentials produced by an [`AuthProvider`], ready to attach to an outgoing request.
+#[derive(Debug, Clone)]
+pub enum Credentials {
+    BearerToken {
+        token: String,
+        /// When this token stops being valid, if known. `None` means the
+        /// provider has no way to tell (e.g. a token response that omitted
+        /// `expires_in`).
+        expires_at: Option<Instant>,
+    },
+}
+
+/// A source of Azure credentials.
+///
+/// Implementations may be interactive (device code, browser login) or
+/// non-interactive (a pre-obtained token), but all of them boil down to
+/// producing a [`Credentials`] value on demand.
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    async fn get_credentials(&self) -> Result<Credentials>;
+
+    /// Human-readable name of this auth method, used in logs and prompts.
+    fn method_name(&self) -> &'static str;
+}
+
+/// Converts a Unix-epoch timestamp (as returned by token endpoints and
+/// embedded in JWT `exp` claims) into an [`Instant`], anchored to "now".
+pub(crate) fn instant_from_epoch_secs(epoch_secs: u64) -> Instant {
+    let now_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let now = Instant::now();
+    if epoch_secs > now_epoch {
+        now + Duration::from_secs(epoch_secs - now_epoch)
+    } else {
+        now
+    }
+}
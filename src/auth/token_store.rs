@@ -0,0 +1,149 @@
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A cached device-code token, as persisted to disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Seconds since the Unix epoch at which `access_token` stops being valid.
+    pub expires_at: u64,
+}
+
+impl CachedToken {
+    pub fn is_valid(&self) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.expires_at > now
+    }
+}
+
+/// On-disk cache of device-code tokens, keyed by `(tenant_id, client_id, scope)` so
+/// multiple profiles can share the same file without clobbering each other.
+///
+/// Lives at `~/.config/azure-aitoolsconnect/tokens.json` (or the platform
+/// equivalent), mirroring the token-store layer used by xal-rs for caching
+/// Xbox Live tokens.
+pub(crate) struct TokenStore {
+    path: PathBuf,
+}
+
+impl TokenStore {
+    pub fn new() -> Result<Self> {
+        let mut path = dirs::config_dir().ok_or_else(|| {
+            AppError::DeviceCodeAuthFailed("could not determine OS config directory".to_string())
+        })?;
+        path.push("azure-aitoolsconnect");
+        fs::create_dir_all(&path).map_err(|e| {
+            AppError::DeviceCodeAuthFailed(format!("failed to create config directory: {}", e))
+        })?;
+        path.push("tokens.json");
+        Ok(Self { path })
+    }
+
+    /// Joins with the ASCII unit separator rather than something like `/`,
+    /// since tenant/client ids and scopes are free-form strings and could in
+    /// principle contain a `/` themselves, which would let two distinct
+    /// (tenant_id, client_id, scope) triples collide on the same key.
+    fn key(tenant_id: &str, client_id: &str, scope: &str) -> String {
+        format!("{}\u{1f}{}\u{1f}{}", tenant_id, client_id, scope)
+    }
+
+    fn read_all(&self) -> HashMap<String, CachedToken> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn load(&self, tenant_id: &str, client_id: &str, scope: &str) -> Option<CachedToken> {
+        self.read_all()
+            .get(&Self::key(tenant_id, client_id, scope))
+            .cloned()
+    }
+
+    pub fn save(&self, tenant_id: &str, client_id: &str, scope: &str, token: CachedToken) -> Result<()> {
+        let mut all = self.read_all();
+        all.insert(Self::key(tenant_id, client_id, scope), token);
+
+        let serialized = serde_json::to_string_pretty(&all).map_err(|e| {
+            AppError::DeviceCodeAuthFailed(format!("failed to serialize token cache: {}", e))
+        })?;
+        fs::write(&self.path, serialized).map_err(|e| {
+            AppError::DeviceCodeAuthFailed(format!("failed to write token cache: {}", e))
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `TokenStore` backed by a scratch file under the OS temp dir, unique
+    /// per test so parallel test runs don't clobber each other.
+    fn temp_store(name: &str) -> TokenStore {
+        let path = std::env::temp_dir().join(format!(
+            "azure-aitoolsconnect-test-{}-{}.json",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_file(&path);
+        TokenStore { path }
+    }
+
+    #[test]
+    fn test_cached_token_is_valid_boundary() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let expired = CachedToken {
+            access_token: "t".to_string(),
+            refresh_token: None,
+            expires_at: now.saturating_sub(1),
+        };
+        assert!(!expired.is_valid());
+
+        let still_valid = CachedToken {
+            access_token: "t".to_string(),
+            refresh_token: None,
+            expires_at: now + 60,
+        };
+        assert!(still_valid.is_valid());
+    }
+
+    #[test]
+    fn test_save_then_load_round_trip() {
+        let store = temp_store("round-trip");
+        let token = CachedToken {
+            access_token: "access-123".to_string(),
+            refresh_token: Some("refresh-456".to_string()),
+            expires_at: 9_999_999_999,
+        };
+
+        store
+            .save("tenant", "client", "scope", token.clone())
+            .unwrap();
+
+        let loaded = store.load("tenant", "client", "scope").unwrap();
+        assert_eq!(loaded.access_token, token.access_token);
+        assert_eq!(loaded.refresh_token, token.refresh_token);
+        assert_eq!(loaded.expires_at, token.expires_at);
+
+        let _ = fs::remove_file(&store.path);
+    }
+
+    #[test]
+    fn test_load_missing_key_returns_none() {
+        let store = temp_store("missing-key");
+        assert!(store.load("tenant", "client", "scope").is_none());
+    }
+}
@@ -0,0 +1,28 @@
+/// Which Azure cloud instance to talk to.
+///
+/// Affects the AAD login endpoint and the Cognitive Services audience used
+/// when requesting tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cloud {
+    Global,
+    China,
+}
+
+impl Cloud {
+    /// Base URL of the Azure AD login endpoint for this cloud.
+    pub fn login_endpoint(&self) -> &'static str {
+        match self {
+            Cloud::Global => "https://login.microsoftonline.com",
+            Cloud::China => "https://login.partner.microsoftonline.cn",
+        }
+    }
+
+    /// `.default` scope to request for Cognitive Services access tokens in
+    /// this cloud.
+    pub fn cognitive_services_scope(&self) -> &'static str {
+        match self {
+            Cloud::Global => "https://cognitiveservices.azure.com/.default",
+            Cloud::China => "https://cognitiveservices.azure.cn/.default",
+        }
+    }
+}
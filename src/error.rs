@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, AppError>;
+
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("device code authentication failed: {0}")]
+    DeviceCodeAuthFailed(String),
+
+    #[error("invalid bearer token: {0}")]
+    InvalidBearerToken(String),
+
+    #[error("authorization code authentication failed: {0}")]
+    AuthCodeAuthFailed(String),
+
+    #[error("client credentials authentication failed: {0}")]
+    ClientCredentialsAuthFailed(String),
+
+    #[error("bearer token expired at unix time {expired_at}")]
+    ExpiredToken { expired_at: u64 },
+}